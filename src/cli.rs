@@ -8,9 +8,9 @@ pub struct Args {
     #[arg(long, short, value_enum, value_name = "TYPE")]
     pub build_type: Option<BuildType>,
 
-    /// IP of the ghost
+    /// IP of the ghost; pass multiple times to deploy to several ghosts
     #[arg(long, short, value_name = "IPv4")]
-    pub ip: Option<Ipv4Addr>,
+    pub ip: Vec<Ipv4Addr>,
 
     /// Build and upload only Stormcloud Daemon
     #[arg(long, short = 'd', conflicts_with = "only_runner")]
@@ -43,6 +43,75 @@ pub struct Args {
     /// Swap to this dir before building Stormcloud
     #[arg(long, short = 'C', value_hint = ValueHint::DirPath, value_name = "DIR")]
     pub working_dir: Option<PathBuf>,
+
+    /// Load defaults from this KEY=VALUE file instead of discovering .dewploy
+    #[arg(long, value_hint = ValueHint::FilePath, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Root directory remote paths are resolved under (default /a)
+    #[arg(long, value_name = "PATH")]
+    pub remote_root: Option<String>,
+
+    /// SSH user used for every remote command instead of root
+    #[arg(long, value_name = "USER")]
+    pub remote_user: Option<String>,
+
+    /// Template for the daemon's remote path ({remote_root}, {component}, {build_type}, {version})
+    #[arg(long, value_name = "TEMPLATE")]
+    pub daemon_remote_path: Option<String>,
+
+    /// Template for the runner's remote path ({remote_root}, {component}, {build_type}, {version})
+    #[arg(long, value_name = "TEMPLATE")]
+    pub runner_remote_path: Option<String>,
+
+    /// Template for cloudbuster's remote path ({remote_root}, {component}, {build_type}, {version})
+    #[arg(long, value_name = "TEMPLATE")]
+    pub cloudbuster_remote_path: Option<String>,
+
+    /// Template for the remote logs path removed before redeploying ({remote_root})
+    #[arg(long, value_name = "TEMPLATE")]
+    pub logs_remote_path: Option<String>,
+
+    /// Stormlet version embedded in the runner's remote path
+    #[arg(long, value_name = "VERSION")]
+    pub stormlet_version: Option<String>,
+
+    /// Print the deploy plan as JSON instead of running it
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Back up replaced binaries, probe the ghost after deploying, and fail the deploy if the probe fails
+    #[arg(long)]
+    pub smoke_test: bool,
+
+    /// When the smoke test fails, restore the previous binaries and restart Stormcloud
+    #[arg(long, requires = "smoke_test")]
+    pub rollback_on_failure: bool,
+
+    /// Seconds the post-deploy health probe may run before it counts as failed (it is not retried)
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        default_value_t = 30,
+        requires = "smoke_test"
+    )]
+    pub smoke_test_timeout: u64,
+
+    /// Number of ghosts to deploy to concurrently
+    #[arg(long, value_name = "N", default_value_t = 1)]
+    pub jobs: usize,
+
+    /// Do not echo each command before running it
+    #[arg(long, short = 'q')]
+    pub quiet: bool,
+
+    /// Cross-compile target triple passed to `cross build` (default: host)
+    #[arg(long, value_name = "TRIPLE")]
+    pub target: Option<String>,
+
+    /// Binary used to strip built binaries instead of plain `strip`
+    #[arg(long, value_name = "PROGRAM")]
+    pub strip_bin: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, ValueEnum)]
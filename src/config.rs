@@ -0,0 +1,52 @@
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Name of the project-local config file discovered by walking up from the
+/// working directory.
+pub const FILE_NAME: &str = ".dewploy";
+
+/// `KEY=VALUE` settings loaded from a `.dewploy` file, consulted after CLI
+/// flags and environment variables and before built-in defaults.
+#[derive(Default)]
+pub struct Config {
+    values: HashMap<String, String>,
+}
+
+impl Config {
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read config file {}", path.display()))?;
+        Ok(Self::parse(&contents))
+    }
+
+    fn parse(contents: &str) -> Self {
+        let values = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Config { values }
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(String::as_str)
+    }
+}
+
+/// Walks up from `start` looking for a `.dewploy` file, returning its path
+/// if one is found.
+pub fn discover(start: &Path) -> Option<PathBuf> {
+    let mut dir = Some(start);
+    while let Some(current) = dir {
+        let candidate = current.join(FILE_NAME);
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+        dir = current.parent();
+    }
+    None
+}
@@ -0,0 +1,33 @@
+use anyhow::{bail, Result};
+use std::process::Command;
+
+/// Runs `command` to completion, echoing it first unless `quiet`. `prefix`
+/// tags the echoed line (e.g. `"[1.2.3.4]"`) so concurrent `--jobs` workers'
+/// output doesn't interleave unattributably; pass `""` for steps that don't
+/// run against a particular ghost. On a non-zero exit this distinguishes a
+/// clean exit code from death by signal, since the two call for different
+/// diagnosis (a bug in the remote script versus e.g. `ssh` getting killed).
+pub fn run(command: &mut Command, context: &str, prefix: &str, quiet: bool) -> Result<()> {
+    if !quiet {
+        pretty_print(command, prefix);
+    }
+
+    let status = command.status()?;
+    if status.success() {
+        return Ok(());
+    }
+
+    match status.code() {
+        Some(code) => bail!("{context}: {command:?} exited with code {code}"),
+        None => bail!("{context}: {command:?} terminated by signal"),
+    }
+}
+
+pub fn pretty_print(command: &Command, prefix: &str) {
+    let rendered = format!("{:?}", command).replace('\"', "");
+    if prefix.is_empty() {
+        println!("\x1b[1;33m{rendered}\x1b[0m");
+    } else {
+        println!("\x1b[1;33m{prefix} {rendered}\x1b[0m");
+    }
+}
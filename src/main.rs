@@ -1,17 +1,40 @@
 mod cli;
+mod config;
+mod exec;
+mod plan;
+mod remote;
+mod smoke;
+mod target;
 
 use crate::cli::{Args, BuildType};
+use crate::config::Config;
+use crate::plan::{SharedPlan, Step};
+use crate::remote::RemoteLayout;
+use crate::smoke::Backup;
+use crate::target::Toolchain;
 use anyhow::{bail, Result};
 use clap::Parser;
-use std::{net::Ipv4Addr, path::PathBuf, process::Command};
+use std::{
+    net::Ipv4Addr,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 const TARGET_DIR: &str = "target-deploy";
 
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    let build_type = parse_build_type(&args)?;
-    let ip = parse_ip(&args)?;
+    let search_root = match &args.working_dir {
+        Some(dir) => dir.clone(),
+        None => std::env::current_dir()?,
+    };
+    let config = load_config(&args, &search_root)?;
+
+    let build_type = parse_build_type(&args, &config)?;
+    let ips = parse_ips(&args, &config)?;
+    let remote = RemoteLayout::resolve(&args, &config);
+    let toolchain = Toolchain::resolve(&args, &config);
     let Args {
         only_daemon,
         only_runner,
@@ -21,36 +44,80 @@ fn main() -> Result<()> {
         keep_logs,
         no_strip,
         working_dir,
+        dry_run,
+        smoke_test,
+        rollback_on_failure,
+        smoke_test_timeout,
+        jobs,
+        quiet,
         ..
     } = args;
 
+    let keep_logs = resolve_flag(keep_logs, &config, "KEEP_LOGS");
+    let no_strip = resolve_flag(no_strip, &config, "NO_STRIP");
+
     switch_to_working_dir(working_dir)?;
 
-    if !no_stop {
-        stop_stormcloud(ip)?;
-    }
+    let plan = SharedPlan::default();
 
-    deploy_project(
+    build_and_strip(
         build_type,
-        ip,
         only_daemon,
         only_runner,
         with_cloudbuster,
         no_strip,
+        dry_run,
+        quiet,
+        &toolchain,
+        &plan,
     )?;
 
-    if !keep_logs {
-        remove_logs(ip)?;
+    let results = deploy_to_hosts(DeployFleet {
+        ips: &ips,
+        jobs: jobs.max(1),
+        build_type,
+        only_daemon,
+        only_runner,
+        with_cloudbuster,
+        no_stop,
+        no_start,
+        keep_logs,
+        dry_run,
+        quiet,
+        smoke_test,
+        rollback_on_failure,
+        smoke_test_timeout,
+        plan: &plan,
+        remote: &remote,
+        toolchain: &toolchain,
+    });
+
+    if dry_run {
+        plan.print()?;
+    } else {
+        print_summary(&results);
     }
 
-    if !no_start {
-        start_stormcloud(ip)?;
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    if failed > 0 {
+        bail!("{failed} of {} ghost(s) failed to deploy", results.len());
     }
 
     Ok(())
 }
 
-fn parse_build_type(args: &Args) -> Result<BuildType> {
+fn load_config(args: &Args, search_root: &Path) -> Result<Config> {
+    if let Some(path) = &args.config {
+        return Config::load(path);
+    }
+
+    match config::discover(search_root) {
+        Some(path) => Config::load(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+fn parse_build_type(args: &Args, config: &Config) -> Result<BuildType> {
     if let Some(build_type) = args.build_type {
         return Ok(build_type);
     }
@@ -61,21 +128,58 @@ fn parse_build_type(args: &Args) -> Result<BuildType> {
         }
     }
 
-    bail!("STORMCLOUD_BUILD_TYPE env var must be defined or --build-type must be supplied");
+    if let Some(build_type_cfg) = config.get("BUILD_TYPE") {
+        if let Ok(build_type) = build_type_cfg.parse() {
+            return Ok(build_type);
+        }
+    }
+
+    bail!(
+        "STORMCLOUD_BUILD_TYPE env var, BUILD_TYPE in .dewploy, or --build-type must be supplied"
+    );
 }
 
-fn parse_ip(args: &Args) -> Result<Ipv4Addr> {
-    if let Some(ip) = args.ip {
-        return Ok(ip);
+fn parse_ips(args: &Args, config: &Config) -> Result<Vec<Ipv4Addr>> {
+    if !args.ip.is_empty() {
+        return Ok(args.ip.clone());
     }
 
     if let Ok(ip_env) = std::env::var("GHOST_IP") {
-        if let Ok(ip) = ip_env.parse() {
-            return Ok(ip);
+        if let Some(ips) = parse_ip_list(&ip_env) {
+            return Ok(ips);
+        }
+    }
+
+    if let Some(ip_cfg) = config.get("GHOST_IP") {
+        if let Some(ips) = parse_ip_list(ip_cfg) {
+            return Ok(ips);
         }
     }
 
-    bail!("GHOST_IP env var must be defined or --ip must be supplied");
+    bail!("GHOST_IP env var, GHOST_IP in .dewploy, or --ip must be supplied");
+}
+
+fn parse_ip_list(value: &str) -> Option<Vec<Ipv4Addr>> {
+    let ips = value
+        .split(',')
+        .map(|ip| ip.trim().parse())
+        .collect::<Result<Vec<Ipv4Addr>, _>>()
+        .ok()?;
+
+    (!ips.is_empty()).then_some(ips)
+}
+
+/// Resolves a boolean CLI flag against its config-file fallback: an explicit
+/// `true` always wins, otherwise the `.dewploy` value is used.
+fn resolve_flag(explicit: bool, config: &Config, key: &str) -> bool {
+    if explicit {
+        return true;
+    }
+
+    config
+        .get(key)
+        .map(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"))
+        .unwrap_or(false)
 }
 
 fn switch_to_working_dir(working_dir: Option<PathBuf>) -> Result<()> {
@@ -86,90 +190,307 @@ fn switch_to_working_dir(working_dir: Option<PathBuf>) -> Result<()> {
     Ok(())
 }
 
-fn deploy_project(
+#[allow(clippy::too_many_arguments)]
+fn build_and_strip(
     build_type: BuildType,
-    ip: Ipv4Addr,
     only_daemon: bool,
     only_runner: bool,
     with_cloudbuster: bool,
     no_strip: bool,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
 ) -> Result<()> {
     if !only_runner {
-        build_daemon(build_type)?;
+        build_daemon(build_type, dry_run, quiet, toolchain, plan)?;
     }
 
     if !only_daemon {
-        build_runner(build_type)?;
+        build_runner(build_type, dry_run, quiet, toolchain, plan)?;
     }
 
     if with_cloudbuster {
-        build_cloudbuster(build_type)?;
+        build_cloudbuster(build_type, dry_run, quiet, toolchain, plan)?;
     }
 
     if !no_strip {
         if !only_runner {
-            strip_daemon(build_type)?;
+            strip_daemon(build_type, dry_run, quiet, toolchain, plan)?;
         }
         if !only_daemon {
-            strip_runner(build_type)?;
+            strip_runner(build_type, dry_run, quiet, toolchain, plan)?;
         }
         if with_cloudbuster {
-            strip_cloudbuster(build_type)?;
+            strip_cloudbuster(build_type, dry_run, quiet, toolchain, plan)?;
         }
     }
 
+    Ok(())
+}
+
+/// Everything [`deploy_to_hosts`] needs, bundled up since it fans out over
+/// every ghost in `ips`.
+struct DeployFleet<'a> {
+    ips: &'a [Ipv4Addr],
+    jobs: usize,
+    build_type: BuildType,
+    only_daemon: bool,
+    only_runner: bool,
+    with_cloudbuster: bool,
+    no_stop: bool,
+    no_start: bool,
+    keep_logs: bool,
+    dry_run: bool,
+    quiet: bool,
+    smoke_test: bool,
+    rollback_on_failure: bool,
+    smoke_test_timeout: u64,
+    plan: &'a SharedPlan,
+    remote: &'a RemoteLayout,
+    toolchain: &'a Toolchain,
+}
+
+/// Runs the stop/upload/remove-logs/start sequence against every ghost in
+/// `fleet.ips`, up to `fleet.jobs` at a time, and reports one result per
+/// ghost so a single bad host doesn't abort the others.
+fn deploy_to_hosts(fleet: DeployFleet) -> Vec<(Ipv4Addr, Result<()>)> {
+    let mut results = Vec::with_capacity(fleet.ips.len());
+
+    for chunk in fleet.ips.chunks(fleet.jobs) {
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = chunk
+                .iter()
+                .map(|&ip| {
+                    let plan = fleet.plan.clone();
+                    scope.spawn(move || {
+                        let result = deploy_to_host(
+                            ip,
+                            fleet.build_type,
+                            fleet.only_daemon,
+                            fleet.only_runner,
+                            fleet.with_cloudbuster,
+                            fleet.no_stop,
+                            fleet.no_start,
+                            fleet.keep_logs,
+                            fleet.dry_run,
+                            fleet.quiet,
+                            fleet.smoke_test,
+                            fleet.rollback_on_failure,
+                            fleet.smoke_test_timeout,
+                            &plan,
+                            fleet.remote,
+                            fleet.toolchain,
+                        );
+                        (ip, result)
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                results.push(handle.join().expect("deploy thread panicked"));
+            }
+        });
+    }
+
+    results
+}
+
+#[allow(clippy::too_many_arguments)]
+fn deploy_to_host(
+    ip: Ipv4Addr,
+    build_type: BuildType,
+    only_daemon: bool,
+    only_runner: bool,
+    with_cloudbuster: bool,
+    no_stop: bool,
+    no_start: bool,
+    keep_logs: bool,
+    dry_run: bool,
+    quiet: bool,
+    smoke_test: bool,
+    rollback_on_failure: bool,
+    smoke_test_timeout: u64,
+    plan: &SharedPlan,
+    remote: &RemoteLayout,
+    toolchain: &Toolchain,
+) -> Result<()> {
+    if !dry_run {
+        println!("[{ip}] deploying");
+    }
+
+    let mut backups = Vec::new();
+
+    if !no_stop {
+        stop_stormcloud(ip, dry_run, quiet, plan, remote)?;
+    }
+
     if !only_runner {
-        upload_daemon(build_type, ip)?;
+        upload_daemon(
+            build_type,
+            ip,
+            dry_run,
+            quiet,
+            smoke_test,
+            plan,
+            &mut backups,
+            remote,
+            toolchain,
+        )?;
     }
 
     if !only_daemon {
-        upload_runner(build_type, ip)?;
+        upload_runner(
+            build_type,
+            ip,
+            dry_run,
+            quiet,
+            smoke_test,
+            plan,
+            &mut backups,
+            remote,
+            toolchain,
+        )?;
     }
 
     if with_cloudbuster {
-        upload_cloudbuster(build_type, ip)?;
+        upload_cloudbuster(
+            build_type,
+            ip,
+            dry_run,
+            quiet,
+            smoke_test,
+            plan,
+            &mut backups,
+            remote,
+            toolchain,
+        )?;
+    }
+
+    if !keep_logs {
+        remove_logs(ip, dry_run, quiet, plan, remote)?;
+    }
+
+    if !no_start {
+        start_stormcloud(ip, dry_run, quiet, plan, remote)?;
+    }
+
+    if smoke_test {
+        if let Err(probe_err) =
+            smoke::run_health_probe(ip, smoke_test_timeout, dry_run, quiet, plan, remote)
+        {
+            if rollback_on_failure {
+                smoke::rollback(ip, &backups, quiet, remote)?;
+                stop_stormcloud(ip, false, quiet, plan, remote)?;
+                start_stormcloud(ip, false, quiet, plan, remote)?;
+            }
+
+            let components: Vec<_> = backups.iter().map(|backup| backup.component).collect();
+            bail!(
+                "[{ip}] smoke test failed after deploying {}: {probe_err}",
+                components.join(", ")
+            );
+        }
+    }
+
+    if !dry_run {
+        println!("[{ip}] deployed");
     }
 
     Ok(())
 }
 
-fn stop_stormcloud(ip: Ipv4Addr) -> Result<()> {
-    let mut command = create_stop_command(ip);
+fn print_summary(results: &[(Ipv4Addr, Result<()>)]) {
+    if results.len() <= 1 {
+        return;
+    }
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!("failed to stop stormcloud on {ip}");
+    let succeeded = results.iter().filter(|(_, result)| result.is_ok()).count();
+    let failed = results.len() - succeeded;
+
+    println!("deploy summary: {succeeded} succeeded, {failed} failed");
+    for (ip, result) in results {
+        match result {
+            Ok(()) => println!("  ok   {ip}"),
+            Err(err) => println!("  FAIL {ip}: {err}"),
+        }
     }
+}
 
-    Ok(())
+fn stop_stormcloud(
+    ip: Ipv4Addr,
+    dry_run: bool,
+    quiet: bool,
+    plan: &SharedPlan,
+    remote: &RemoteLayout,
+) -> Result<()> {
+    let mut command = create_stop_command(ip, remote);
+
+    if dry_run {
+        plan.record(Step::new("ssh", &command).for_host(ip));
+        return Ok(());
+    }
+
+    exec::run(
+        &mut command,
+        &format!("stop stormcloud on {ip}"),
+        &format!("[{ip}]"),
+        quiet,
+    )
 }
 
-fn start_stormcloud(ip: Ipv4Addr) -> Result<()> {
-    let mut command = create_start_command(ip);
+fn start_stormcloud(
+    ip: Ipv4Addr,
+    dry_run: bool,
+    quiet: bool,
+    plan: &SharedPlan,
+    remote: &RemoteLayout,
+) -> Result<()> {
+    let mut command = create_start_command(ip, remote);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!("failed to start stormcloud on {ip}");
+    if dry_run {
+        plan.record(Step::new("ssh", &command).for_host(ip));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!("start stormcloud on {ip}"),
+        &format!("[{ip}]"),
+        quiet,
+    )
 }
 
-fn remove_logs(ip: Ipv4Addr) -> Result<()> {
-    let mut command = create_remove_logs_command(ip);
+fn remove_logs(
+    ip: Ipv4Addr,
+    dry_run: bool,
+    quiet: bool,
+    plan: &SharedPlan,
+    remote: &RemoteLayout,
+) -> Result<()> {
+    let mut command = create_remove_logs_command(ip, remote);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!("failed to remove logs on {ip}");
+    if dry_run {
+        plan.record(Step::new("ssh", &command).for_host(ip));
+        return Ok(());
     }
-    Ok(())
+
+    exec::run(
+        &mut command,
+        &format!("remove logs on {ip}"),
+        &format!("[{ip}]"),
+        quiet,
+    )
 }
 
-fn build_daemon(build_type: BuildType) -> Result<()> {
-    let mut command = create_build_command();
+fn build_daemon(
+    build_type: BuildType,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
+) -> Result<()> {
+    let mut command = create_build_command(toolchain);
 
     command.arg("--package");
     command.arg("stormcloud_daemon");
@@ -181,20 +502,27 @@ fn build_daemon(build_type: BuildType) -> Result<()> {
         command.arg("--release");
     }
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to build {} daemon",
-            build_type.to_string().to_lowercase(),
-        );
+    if dry_run {
+        plan.record(Step::new("build", &command));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!("build {} daemon", build_type.to_string().to_lowercase()),
+        "",
+        quiet,
+    )
 }
 
-fn build_runner(build_type: BuildType) -> Result<()> {
-    let mut command = create_build_command();
+fn build_runner(
+    build_type: BuildType,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
+) -> Result<()> {
+    let mut command = create_build_command(toolchain);
 
     command.arg("--package");
     command.arg("stormrunner_javascript");
@@ -206,20 +534,27 @@ fn build_runner(build_type: BuildType) -> Result<()> {
         command.arg("--release");
     }
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to build {} runner",
-            build_type.to_string().to_lowercase()
-        );
+    if dry_run {
+        plan.record(Step::new("build", &command));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!("build {} runner", build_type.to_string().to_lowercase()),
+        "",
+        quiet,
+    )
 }
 
-fn build_cloudbuster(build_type: BuildType) -> Result<()> {
-    let mut command = create_build_command();
+fn build_cloudbuster(
+    build_type: BuildType,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
+) -> Result<()> {
+    let mut command = create_build_command(toolchain);
 
     command.arg("--package");
     command.arg("cloudbuster");
@@ -231,203 +566,309 @@ fn build_cloudbuster(build_type: BuildType) -> Result<()> {
         command.arg("--release");
     }
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to build {} cloudbuster",
-            build_type.to_string().to_lowercase()
-        );
+    if dry_run {
+        plan.record(Step::new("build", &command));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!(
+            "build {} cloudbuster",
+            build_type.to_string().to_lowercase()
+        ),
+        "",
+        quiet,
+    )
 }
 
-fn strip_daemon(build_type: BuildType) -> Result<()> {
+fn strip_daemon(
+    build_type: BuildType,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
+) -> Result<()> {
     let target_file = format!(
-        "{}/{}/stormcloud_daemon",
-        TARGET_DIR,
-        build_type.to_string().to_lowercase()
+        "{}/stormcloud_daemon",
+        toolchain.profile_dir(TARGET_DIR, &build_type.to_string().to_lowercase())
     );
 
-    let mut command = create_strip_command();
+    let mut command = create_strip_command(toolchain);
     command.arg(target_file);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to strip {} daemon",
-            build_type.to_string().to_lowercase(),
-        )
+    if dry_run {
+        plan.record(Step::new("strip", &command));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!("strip {} daemon", build_type.to_string().to_lowercase()),
+        "",
+        quiet,
+    )
 }
 
-fn strip_runner(build_type: BuildType) -> Result<()> {
+fn strip_runner(
+    build_type: BuildType,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
+) -> Result<()> {
     let target_file = format!(
-        "{}/{}/stormrunner_javascript",
-        TARGET_DIR,
-        build_type.to_string().to_lowercase()
+        "{}/stormrunner_javascript",
+        toolchain.profile_dir(TARGET_DIR, &build_type.to_string().to_lowercase())
     );
 
-    let mut command = create_strip_command();
+    let mut command = create_strip_command(toolchain);
     command.arg(target_file);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to strip {} runner",
-            build_type.to_string().to_lowercase(),
-        )
+    if dry_run {
+        plan.record(Step::new("strip", &command));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!("strip {} runner", build_type.to_string().to_lowercase()),
+        "",
+        quiet,
+    )
 }
 
-fn strip_cloudbuster(build_type: BuildType) -> Result<()> {
+fn strip_cloudbuster(
+    build_type: BuildType,
+    dry_run: bool,
+    quiet: bool,
+    toolchain: &Toolchain,
+    plan: &SharedPlan,
+) -> Result<()> {
     let target_file = format!(
-        "{}/{}/cloudbuster",
-        TARGET_DIR,
-        build_type.to_string().to_lowercase()
+        "{}/cloudbuster",
+        toolchain.profile_dir(TARGET_DIR, &build_type.to_string().to_lowercase())
     );
 
-    let mut command = create_strip_command();
+    let mut command = create_strip_command(toolchain);
     command.arg(target_file);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to strip {} cloudbuster",
-            build_type.to_string().to_lowercase(),
-        )
+    if dry_run {
+        plan.record(Step::new("strip", &command));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!(
+            "strip {} cloudbuster",
+            build_type.to_string().to_lowercase()
+        ),
+        "",
+        quiet,
+    )
 }
 
-fn upload_daemon(build_type: BuildType, ip: Ipv4Addr) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn upload_daemon(
+    build_type: BuildType,
+    ip: Ipv4Addr,
+    dry_run: bool,
+    quiet: bool,
+    smoke_test: bool,
+    plan: &SharedPlan,
+    backups: &mut Vec<Backup>,
+    remote: &RemoteLayout,
+    toolchain: &Toolchain,
+) -> Result<()> {
     let source_file = format!(
-        "{}/{}/stormcloud_daemon",
-        TARGET_DIR,
-        build_type.to_string().to_lowercase()
+        "{}/stormcloud_daemon",
+        toolchain.profile_dir(TARGET_DIR, &build_type.to_string().to_lowercase())
     );
 
-    let target_file = format!("root@{}:/a/stormcloud/bin/release/stormcloud_daemon", ip,);
+    let remote_path = remote.daemon_path(build_type);
+    let target_file = remote.destination(ip, &remote_path);
+
+    if smoke_test {
+        smoke::backup_remote_file(
+            ip,
+            "stormcloud daemon",
+            &remote_path,
+            dry_run,
+            quiet,
+            plan,
+            backups,
+            remote,
+        )?;
+    }
 
     let mut command = create_upload_command();
-    command.arg(source_file);
-    command.arg(target_file);
+    command.arg(&source_file);
+    command.arg(&target_file);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to upload {} daemon to {}",
-            build_type.to_string().to_lowercase(),
-            ip
-        );
+    if dry_run {
+        plan.record(Step::upload(&command, source_file, target_file).for_host(ip));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!(
+            "upload {} daemon to {ip}",
+            build_type.to_string().to_lowercase()
+        ),
+        &format!("[{ip}]"),
+        quiet,
+    )
 }
 
-fn upload_runner(build_type: BuildType, ip: Ipv4Addr) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn upload_runner(
+    build_type: BuildType,
+    ip: Ipv4Addr,
+    dry_run: bool,
+    quiet: bool,
+    smoke_test: bool,
+    plan: &SharedPlan,
+    backups: &mut Vec<Backup>,
+    remote: &RemoteLayout,
+    toolchain: &Toolchain,
+) -> Result<()> {
     let source_file = format!(
-        "{}/{}/stormrunner_javascript",
-        TARGET_DIR,
-        build_type.to_string().to_lowercase()
+        "{}/stormrunner_javascript",
+        toolchain.profile_dir(TARGET_DIR, &build_type.to_string().to_lowercase())
     );
 
-    let target_file = format!(
-        "root@{}:/a/stormcloud/stormlets/release/deployed/stormlet_javascript@0.0.0/stormrunner_javascript.0.0.0",
-        ip,
-    );
+    let remote_path = remote.runner_path(build_type);
+    let target_file = remote.destination(ip, &remote_path);
+
+    if smoke_test {
+        smoke::backup_remote_file(
+            ip,
+            "stormrunner javascript",
+            &remote_path,
+            dry_run,
+            quiet,
+            plan,
+            backups,
+            remote,
+        )?;
+    }
 
     let mut command = create_upload_command();
-    command.arg(source_file);
-    command.arg(target_file);
+    command.arg(&source_file);
+    command.arg(&target_file);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to upload {} runner to {}",
-            build_type.to_string().to_lowercase(),
-            ip
-        );
+    if dry_run {
+        plan.record(Step::upload(&command, source_file, target_file).for_host(ip));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!(
+            "upload {} runner to {ip}",
+            build_type.to_string().to_lowercase()
+        ),
+        &format!("[{ip}]"),
+        quiet,
+    )
 }
 
-fn upload_cloudbuster(build_type: BuildType, ip: Ipv4Addr) -> Result<()> {
+#[allow(clippy::too_many_arguments)]
+fn upload_cloudbuster(
+    build_type: BuildType,
+    ip: Ipv4Addr,
+    dry_run: bool,
+    quiet: bool,
+    smoke_test: bool,
+    plan: &SharedPlan,
+    backups: &mut Vec<Backup>,
+    remote: &RemoteLayout,
+    toolchain: &Toolchain,
+) -> Result<()> {
     let source_file = format!(
-        "{}/{}/cloudbuster",
-        TARGET_DIR,
-        build_type.to_string().to_lowercase()
+        "{}/cloudbuster",
+        toolchain.profile_dir(TARGET_DIR, &build_type.to_string().to_lowercase())
     );
 
-    let target_file = format!("root@{}:/a/stormcloud/bin/cloudbuster", ip,);
+    let remote_path = remote.cloudbuster_path(build_type);
+    let target_file = remote.destination(ip, &remote_path);
+
+    if smoke_test {
+        smoke::backup_remote_file(
+            ip,
+            "cloudbuster",
+            &remote_path,
+            dry_run,
+            quiet,
+            plan,
+            backups,
+            remote,
+        )?;
+    }
 
     let mut command = create_upload_command();
-    command.arg(source_file);
-    command.arg(target_file);
+    command.arg(&source_file);
+    command.arg(&target_file);
 
-    pretty_print(&command);
-    let status = command.status()?;
-    if !status.success() {
-        bail!(
-            "failed to upload {} cloudbuster to {}",
-            build_type.to_string().to_lowercase(),
-            ip
-        );
+    if dry_run {
+        plan.record(Step::upload(&command, source_file, target_file).for_host(ip));
+        return Ok(());
     }
 
-    Ok(())
+    exec::run(
+        &mut command,
+        &format!(
+            "upload {} cloudbuster to {ip}",
+            build_type.to_string().to_lowercase()
+        ),
+        &format!("[{ip}]"),
+        quiet,
+    )
 }
 
-fn create_stop_command(ip: Ipv4Addr) -> Command {
+fn create_stop_command(ip: Ipv4Addr, remote: &RemoteLayout) -> Command {
     let mut command = Command::new("ssh");
     command
-        .arg(format!("root@{}", ip))
-        .arg("/a/sbin/akamai_run")
+        .arg(remote.host(ip))
+        .arg(remote.akamai_run_path())
         .arg("stop")
         .arg("stormcloud");
     command
 }
 
-fn create_start_command(ip: Ipv4Addr) -> Command {
+fn create_start_command(ip: Ipv4Addr, remote: &RemoteLayout) -> Command {
     let mut command = Command::new("ssh");
     command
-        .arg(format!("root@{}", ip))
-        .arg("/a/sbin/akamai_run")
+        .arg(remote.host(ip))
+        .arg(remote.akamai_run_path())
         .arg("start")
         .arg("stormcloud");
     command
 }
 
-fn create_remove_logs_command(ip: Ipv4Addr) -> Command {
+fn create_remove_logs_command(ip: Ipv4Addr, remote: &RemoteLayout) -> Command {
     let mut command = Command::new("ssh");
     command
-        .arg(format!("root@{}", ip))
+        .arg(remote.host(ip))
         .arg("rm")
         .arg("-rf")
-        .arg("/a/logs/stormcloud");
+        .arg(remote.logs_path());
     command
 }
 
-fn create_build_command() -> Command {
+fn create_build_command(toolchain: &Toolchain) -> Command {
     let mut command = Command::new("cross");
     command.arg("build");
+    if let Some(triple) = toolchain.triple() {
+        command.arg("--target").arg(triple);
+    }
     command
 }
 
-fn create_strip_command() -> Command {
-    let mut command = Command::new("strip");
+fn create_strip_command(toolchain: &Toolchain) -> Command {
+    let mut command = Command::new(toolchain.strip_bin());
     command.arg("--strip-unneeded");
     command
 }
@@ -441,10 +882,3 @@ fn create_upload_command() -> Command {
         .arg("--verbose");
     command
 }
-
-fn pretty_print(command: &Command) {
-    println!(
-        "\x1b[1;33m{}\x1b[0m",
-        format!("{:?}", command).replace('\"', "")
-    );
-}
@@ -0,0 +1,83 @@
+use serde::Serialize;
+use std::net::Ipv4Addr;
+use std::process::Command;
+use std::sync::{Arc, Mutex};
+
+/// One step of a deploy plan, as it would be rendered to `std::process::Command`.
+#[derive(Serialize)]
+pub struct Step {
+    pub kind: &'static str,
+    pub program: String,
+    pub args: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// The ghost this step runs against, absent for steps (like the build)
+    /// that run once ahead of the per-host fan-out.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+}
+
+impl Step {
+    pub fn new(kind: &'static str, command: &Command) -> Self {
+        Step {
+            kind,
+            program: command.get_program().to_string_lossy().into_owned(),
+            args: command
+                .get_args()
+                .map(|arg| arg.to_string_lossy().into_owned())
+                .collect(),
+            source: None,
+            target: None,
+            host: None,
+        }
+    }
+
+    pub fn upload(command: &Command, source: String, target: String) -> Self {
+        Step {
+            source: Some(source),
+            target: Some(target),
+            ..Step::new("upload", command)
+        }
+    }
+
+    /// Tags this step with the ghost it runs against, so steps from
+    /// concurrent `--jobs` workers can be regrouped by host after the fact.
+    pub fn for_host(mut self, ip: Ipv4Addr) -> Self {
+        self.host = Some(ip.to_string());
+        self
+    }
+}
+
+/// Ordered list of steps `dewploy` would run against a ghost, emitted as JSON
+/// when `--dry-run` is passed instead of being executed.
+#[derive(Default, Serialize)]
+pub struct Plan {
+    pub steps: Vec<Step>,
+}
+
+impl Plan {
+    pub fn record(&mut self, step: Step) {
+        self.steps.push(step);
+    }
+
+    pub fn print(&self) -> anyhow::Result<()> {
+        println!("{}", serde_json::to_string_pretty(self)?);
+        Ok(())
+    }
+}
+
+/// A [`Plan`] shared across the per-host deploy threads spawned for `--jobs`.
+#[derive(Clone, Default)]
+pub struct SharedPlan(Arc<Mutex<Plan>>);
+
+impl SharedPlan {
+    pub fn record(&self, step: Step) {
+        self.0.lock().expect("plan lock poisoned").record(step);
+    }
+
+    pub fn print(&self) -> anyhow::Result<()> {
+        self.0.lock().expect("plan lock poisoned").print()
+    }
+}
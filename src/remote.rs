@@ -0,0 +1,105 @@
+use crate::cli::{Args, BuildType};
+use crate::config::Config;
+use std::net::Ipv4Addr;
+
+/// Where on a ghost each component lives, and who to `ssh`/`rsync` as to get
+/// there. Each component's path is a template rendered with `{remote_root}`,
+/// `{component}`, `{build_type}` and `{version}` placeholders, and the logs
+/// path with `{remote_root}` only, so a non-standard layout doesn't require
+/// patching the source.
+pub struct RemoteLayout {
+    user: String,
+    remote_root: String,
+    version: String,
+    daemon_template: String,
+    runner_template: String,
+    cloudbuster_template: String,
+    logs_template: String,
+}
+
+impl RemoteLayout {
+    pub fn resolve(args: &Args, config: &Config) -> Self {
+        RemoteLayout {
+            user: setting(args.remote_user.as_deref(), config, "REMOTE_USER", "root"),
+            remote_root: setting(args.remote_root.as_deref(), config, "REMOTE_ROOT", "/a"),
+            version: setting(
+                args.stormlet_version.as_deref(),
+                config,
+                "STORMLET_VERSION",
+                "0.0.0",
+            ),
+            daemon_template: setting(
+                args.daemon_remote_path.as_deref(),
+                config,
+                "DAEMON_REMOTE_PATH",
+                "{remote_root}/stormcloud/bin/release/{component}",
+            ),
+            runner_template: setting(
+                args.runner_remote_path.as_deref(),
+                config,
+                "RUNNER_REMOTE_PATH",
+                "{remote_root}/stormcloud/stormlets/release/deployed/stormlet_javascript@{version}/{component}.{version}",
+            ),
+            cloudbuster_template: setting(
+                args.cloudbuster_remote_path.as_deref(),
+                config,
+                "CLOUDBUSTER_REMOTE_PATH",
+                "{remote_root}/stormcloud/bin/{component}",
+            ),
+            logs_template: setting(
+                args.logs_remote_path.as_deref(),
+                config,
+                "LOGS_REMOTE_PATH",
+                "{remote_root}/logs/stormcloud",
+            ),
+        }
+    }
+
+    pub fn daemon_path(&self, build_type: BuildType) -> String {
+        self.render(&self.daemon_template, "stormcloud_daemon", build_type)
+    }
+
+    pub fn runner_path(&self, build_type: BuildType) -> String {
+        self.render(&self.runner_template, "stormrunner_javascript", build_type)
+    }
+
+    pub fn cloudbuster_path(&self, build_type: BuildType) -> String {
+        self.render(&self.cloudbuster_template, "cloudbuster", build_type)
+    }
+
+    pub fn logs_path(&self) -> String {
+        self.logs_template
+            .replace("{remote_root}", &self.remote_root)
+    }
+
+    /// Path to the `akamai_run` control script used to stop/start/probe
+    /// Stormcloud, resolved under `remote_root`.
+    pub fn akamai_run_path(&self) -> String {
+        format!("{}/sbin/akamai_run", self.remote_root)
+    }
+
+    /// Renders a `user@ip:remote_path` rsync/ssh destination.
+    pub fn destination(&self, ip: Ipv4Addr, remote_path: &str) -> String {
+        format!("{}@{}:{}", self.user, ip, remote_path)
+    }
+
+    /// Renders a `user@ip` ssh target.
+    pub fn host(&self, ip: Ipv4Addr) -> String {
+        format!("{}@{}", self.user, ip)
+    }
+
+    fn render(&self, template: &str, component: &str, build_type: BuildType) -> String {
+        template
+            .replace("{remote_root}", &self.remote_root)
+            .replace("{component}", component)
+            .replace("{build_type}", &build_type.to_string().to_lowercase())
+            .replace("{version}", &self.version)
+    }
+}
+
+fn setting(explicit: Option<&str>, config: &Config, key: &str, default: &str) -> String {
+    explicit
+        .or_else(|| config.get(key))
+        .unwrap_or(default)
+        .to_string()
+}
@@ -0,0 +1,122 @@
+use crate::exec;
+use crate::plan::{SharedPlan, Step};
+use crate::remote::RemoteLayout;
+use anyhow::Result;
+use std::net::Ipv4Addr;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A remote file saved aside before an upload, so it can be restored if the
+/// post-deploy smoke test fails.
+pub struct Backup {
+    pub component: &'static str,
+    pub remote_path: String,
+    pub backup_path: String,
+}
+
+/// `ssh`es a `cp` of `remote_path` to a timestamped `.bak` path on `ip`, and
+/// records it in `backups` so it can be restored by [`rollback`].
+#[allow(clippy::too_many_arguments)]
+pub fn backup_remote_file(
+    ip: Ipv4Addr,
+    component: &'static str,
+    remote_path: &str,
+    dry_run: bool,
+    quiet: bool,
+    plan: &SharedPlan,
+    backups: &mut Vec<Backup>,
+    remote: &RemoteLayout,
+) -> Result<()> {
+    let backup_path = format!("{remote_path}.bak.{}", unix_timestamp()?);
+
+    let mut command = Command::new("ssh");
+    command
+        .arg(remote.host(ip))
+        .arg("cp")
+        .arg(remote_path)
+        .arg(&backup_path);
+
+    if dry_run {
+        plan.record(Step::new("ssh", &command).for_host(ip));
+    } else {
+        exec::run(
+            &mut command,
+            &format!("back up {component} on {ip}"),
+            &format!("[{ip}]"),
+            quiet,
+        )?;
+    }
+
+    backups.push(Backup {
+        component,
+        remote_path: remote_path.to_string(),
+        backup_path,
+    });
+
+    Ok(())
+}
+
+/// Probes the freshly deployed Stormcloud on `ip` once, failing if the probe
+/// reports unhealthy or runs longer than `timeout_secs`; it is not retried.
+pub fn run_health_probe(
+    ip: Ipv4Addr,
+    timeout_secs: u64,
+    dry_run: bool,
+    quiet: bool,
+    plan: &SharedPlan,
+    remote: &RemoteLayout,
+) -> Result<()> {
+    let mut command = Command::new("timeout");
+    command
+        .arg(timeout_secs.to_string())
+        .arg("ssh")
+        .arg(remote.host(ip))
+        .arg(remote.akamai_run_path())
+        .arg("status")
+        .arg("stormcloud");
+
+    if dry_run {
+        plan.record(Step::new("ssh", &command).for_host(ip));
+        return Ok(());
+    }
+
+    exec::run(
+        &mut command,
+        &format!("stormcloud did not report healthy on {ip} within {timeout_secs}s"),
+        &format!("[{ip}]"),
+        quiet,
+    )
+}
+
+/// Restores every backed up file on `ip`, in order.
+pub fn rollback(
+    ip: Ipv4Addr,
+    backups: &[Backup],
+    quiet: bool,
+    remote: &RemoteLayout,
+) -> Result<()> {
+    for backup in backups {
+        let mut command = Command::new("ssh");
+        command
+            .arg(remote.host(ip))
+            .arg("cp")
+            .arg(&backup.backup_path)
+            .arg(&backup.remote_path);
+
+        exec::run(
+            &mut command,
+            &format!(
+                "restore {} on {ip} from {}",
+                backup.component, backup.backup_path
+            ),
+            &format!("[{ip}]"),
+            quiet,
+        )?;
+    }
+
+    Ok(())
+}
+
+fn unix_timestamp() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
@@ -0,0 +1,59 @@
+use crate::cli::Args;
+use crate::config::Config;
+
+/// The cross-compilation target: a `cross`/`rustc` target triple, and the
+/// binary used to strip debug symbols from binaries built for it, since a
+/// cross-built binary usually can't be stripped with the host's `strip`.
+pub struct Toolchain {
+    triple: Option<String>,
+    strip_bin: String,
+}
+
+impl Toolchain {
+    pub fn resolve(args: &Args, config: &Config) -> Self {
+        let triple = args
+            .target
+            .clone()
+            .or_else(|| std::env::var("CROSS_TARGET").ok())
+            .or_else(|| config.get("TARGET_TRIPLE").map(str::to_string));
+
+        let strip_bin = args
+            .strip_bin
+            .clone()
+            .or_else(|| config.get("STRIP_BIN").map(str::to_string))
+            .unwrap_or_else(|| default_strip_bin(triple.as_deref()));
+
+        Toolchain { triple, strip_bin }
+    }
+
+    pub fn triple(&self) -> Option<&str> {
+        self.triple.as_deref()
+    }
+
+    pub fn strip_bin(&self) -> &str {
+        &self.strip_bin
+    }
+
+    /// Directory built binaries land under for this target, relative to
+    /// `target_dir`: `<target_dir>/<triple>/<profile>` when cross-compiling,
+    /// `<target_dir>/<profile>` for the host.
+    pub fn profile_dir(&self, target_dir: &str, profile: &str) -> String {
+        match &self.triple {
+            Some(triple) => format!("{target_dir}/{triple}/{profile}"),
+            None => format!("{target_dir}/{profile}"),
+        }
+    }
+}
+
+/// Best-effort default strip binary for a handful of common cross-compile
+/// triples; falls back to the host `strip` for anything else.
+fn default_strip_bin(triple: Option<&str>) -> String {
+    match triple {
+        Some("aarch64-unknown-linux-gnu") => "aarch64-linux-gnu-strip",
+        Some("aarch64-unknown-linux-musl") => "aarch64-linux-musl-strip",
+        Some("armv7-unknown-linux-gnueabihf") => "arm-linux-gnueabihf-strip",
+        Some("x86_64-unknown-linux-musl") => "x86_64-linux-musl-strip",
+        _ => "strip",
+    }
+    .to_string()
+}